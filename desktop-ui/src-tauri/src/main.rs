@@ -1,9 +1,15 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use std::fs;
+use std::sync::Mutex;
+use std::time::Duration;
 use tauri::Manager;
 use serde::{Deserialize, Serialize};
 use reqwest::Client;
+use futures_util::StreamExt;
+use uuid::Uuid;
+use std::io::Write;
+use base64::Engine;
 
 // --- Data Structures ---
 
@@ -15,6 +21,468 @@ struct Question {
     hint: Option<String>,
 }
 
+/// Shared HTTP backend configuration, loaded from `mcp_agent.config.yaml`
+/// and kept in Tauri managed state so every command talks to the same
+/// backend without re-reading the config file on each call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HttpConfig {
+    base_url: String,
+    connect_timeout_secs: u64,
+    read_timeout_secs: u64,
+    max_redirects: usize,
+    allow_compression: bool,
+    retry_count: u32,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        HttpConfig {
+            base_url: "http://localhost:8000".to_string(),
+            connect_timeout_secs: 10,
+            read_timeout_secs: 60,
+            max_redirects: 5,
+            allow_compression: true,
+            retry_count: 2,
+        }
+    }
+}
+
+impl HttpConfig {
+    /// Loads overrides from the `http` section of `mcp_agent.config.yaml`,
+    /// falling back to defaults when the file or section is missing.
+    fn load_from_app_config(app_handle: &tauri::AppHandle) -> Self {
+        let mut config = HttpConfig::default();
+
+        let content = match read_config(app_handle.clone()) {
+            Ok(content) => content,
+            Err(_) => return config,
+        };
+
+        let parsed: serde_yaml::Value = match serde_yaml::from_str(&content) {
+            Ok(parsed) => parsed,
+            Err(_) => return config,
+        };
+
+        let http_section = match parsed.get("http") {
+            Some(section) => section,
+            None => return config,
+        };
+
+        if let Some(base_url) = http_section.get("base_url").and_then(|v| v.as_str()) {
+            config.base_url = base_url.to_string();
+        }
+        if let Some(v) = http_section.get("connect_timeout_secs").and_then(|v| v.as_u64()) {
+            config.connect_timeout_secs = v;
+        }
+        if let Some(v) = http_section.get("read_timeout_secs").and_then(|v| v.as_u64()) {
+            config.read_timeout_secs = v;
+        }
+        if let Some(v) = http_section.get("max_redirects").and_then(|v| v.as_u64()) {
+            config.max_redirects = v as usize;
+        }
+        if let Some(v) = http_section.get("allow_compression").and_then(|v| v.as_bool()) {
+            config.allow_compression = v;
+        }
+        if let Some(v) = http_section.get("retry_count").and_then(|v| v.as_u64()) {
+            config.retry_count = v as u32;
+        }
+
+        config
+    }
+
+    fn build_client(&self) -> Client {
+        Client::builder()
+            .connect_timeout(Duration::from_secs(self.connect_timeout_secs))
+            .timeout(Duration::from_secs(self.read_timeout_secs))
+            .redirect(reqwest::redirect::Policy::limited(self.max_redirects))
+            .gzip(self.allow_compression)
+            .build()
+            .unwrap_or_else(|_| Client::new())
+    }
+}
+
+/// State shared across commands: the resolved config plus the single
+/// `reqwest::Client` built from it, so we don't pay connection-pool setup
+/// cost on every request.
+struct HttpState {
+    config: Mutex<HttpConfig>,
+    client: Mutex<Client>,
+}
+
+impl HttpState {
+    fn new(config: HttpConfig) -> Self {
+        let client = config.build_client();
+        HttpState {
+            config: Mutex::new(config),
+            client: Mutex::new(client),
+        }
+    }
+}
+
+/// Response body handling for a request: most endpoints return JSON, but
+/// some (e.g. a generated zip) need to come back as raw bytes or text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResponseType {
+    Json,
+    Text,
+    Binary,
+}
+
+/// Per-call overrides for the shared HTTP helpers. Any field left at its
+/// default falls back to the managed `HttpConfig`.
+#[derive(Debug, Clone)]
+struct HttpOptions {
+    method: reqwest::Method,
+    headers: Vec<(String, String)>,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    follow_redirects: bool,
+    max_redirections: Option<usize>,
+    allow_compression: Option<bool>,
+    response_type: ResponseType,
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        HttpOptions {
+            method: reqwest::Method::GET,
+            headers: Vec::new(),
+            connect_timeout: None,
+            read_timeout: None,
+            timeout: None,
+            follow_redirects: true,
+            max_redirections: None,
+            allow_compression: None,
+            response_type: ResponseType::Json,
+        }
+    }
+}
+
+/// Result of a raw HTTP call before it is decoded into the caller's
+/// expected shape.
+enum HttpResponseBody {
+    Json(serde_json::Value),
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+/// Identifies an artifact cached by `cache_stream_artifacts` under
+/// `deepcode://artifact/<job_id>/<path>`, so the frontend can discover and
+/// load it without the path happening to be embedded in the streamed text.
+#[derive(Debug, Clone, Serialize)]
+struct GenerationArtifactInfo {
+    path: String,
+    mime: String,
+}
+
+/// A `generation_progress` frame emitted while a streaming generation job
+/// is in flight. `partial_text` accumulates everything streamed so far for
+/// the whole job, across all phases, so the frontend can just render it
+/// directly.
+/// `artifacts` accumulates every artifact cached so far for this job.
+#[derive(Debug, Clone, Serialize)]
+struct GenerationProgress {
+    job_id: String,
+    phase: String,
+    partial_text: String,
+    tokens_generated: Option<u64>,
+    artifacts: Vec<GenerationArtifactInfo>,
+}
+
+/// Emitted once when a streaming generation job finishes successfully.
+/// `artifacts` lists every artifact cached over the job's lifetime.
+#[derive(Debug, Clone, Serialize)]
+struct GenerationDone {
+    job_id: String,
+    result: String,
+    artifacts: Vec<GenerationArtifactInfo>,
+}
+
+/// Emitted once when a streaming generation job fails or is cancelled.
+#[derive(Debug, Clone, Serialize)]
+struct GenerationErrorPayload {
+    job_id: String,
+    error: String,
+}
+
+/// A single newline-delimited JSON frame the backend streams back while a
+/// generation job runs.
+#[derive(Debug, Deserialize)]
+struct GenerationStreamFrame {
+    #[serde(default)]
+    phase: String,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    tokens: Option<u64>,
+    #[serde(default)]
+    done: bool,
+    #[serde(default)]
+    artifacts: Vec<GeneratedArtifactFrame>,
+}
+
+/// An artifact (source file, diagram, zip, ...) attached to a stream frame,
+/// inlined as base64 so it can be cached locally without a follow-up fetch.
+#[derive(Debug, Deserialize)]
+struct GeneratedArtifactFrame {
+    path: String,
+    #[serde(default)]
+    mime: Option<String>,
+    content_base64: String,
+}
+
+/// Configuration for supervising the Python backend as a child process,
+/// loaded from the `backend` section of `mcp_agent.config.yaml`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BackendProcessConfig {
+    command: String,
+    args: Vec<String>,
+    health_path: String,
+    startup_timeout_secs: u64,
+    max_restart_attempts: u32,
+    log_path: String,
+}
+
+impl Default for BackendProcessConfig {
+    fn default() -> Self {
+        BackendProcessConfig {
+            command: "python".to_string(),
+            args: vec!["-m".to_string(), "mcp_agent.server".to_string()],
+            health_path: "/health".to_string(),
+            startup_timeout_secs: 30,
+            max_restart_attempts: 5,
+            log_path: "mcp_agent.log".to_string(),
+        }
+    }
+}
+
+impl BackendProcessConfig {
+    fn load_from_app_config(app_handle: &tauri::AppHandle) -> Self {
+        let mut process_config = BackendProcessConfig::default();
+
+        let content = match read_config(app_handle.clone()) {
+            Ok(content) => content,
+            Err(_) => return process_config,
+        };
+
+        let parsed: serde_yaml::Value = match serde_yaml::from_str(&content) {
+            Ok(parsed) => parsed,
+            Err(_) => return process_config,
+        };
+
+        let backend_section = match parsed.get("backend") {
+            Some(section) => section,
+            None => return process_config,
+        };
+
+        if let Some(command) = backend_section.get("command").and_then(|v| v.as_str()) {
+            process_config.command = command.to_string();
+        }
+        if let Some(args) = backend_section.get("args").and_then(|v| v.as_sequence()) {
+            process_config.args = args.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect();
+        }
+        if let Some(health_path) = backend_section.get("health_path").and_then(|v| v.as_str()) {
+            process_config.health_path = health_path.to_string();
+        }
+        if let Some(v) = backend_section.get("startup_timeout_secs").and_then(|v| v.as_u64()) {
+            process_config.startup_timeout_secs = v;
+        }
+        if let Some(v) = backend_section.get("max_restart_attempts").and_then(|v| v.as_u64()) {
+            process_config.max_restart_attempts = v as u32;
+        }
+        if let Some(log_path) = backend_section.get("log_path").and_then(|v| v.as_str()) {
+            process_config.log_path = log_path.to_string();
+        }
+
+        process_config
+    }
+}
+
+/// Lifecycle state of the supervised backend process, mirrored to the
+/// frontend via `backend_state` events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum BackendState {
+    Starting,
+    Ready,
+    Crashed,
+    Stopped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct BackendStateEvent {
+    state: BackendState,
+    detail: Option<String>,
+}
+
+/// Owns the supervised backend child process and its current lifecycle
+/// state, along with the monitor task watching for crashes.
+struct BackendSupervisor {
+    state: Mutex<BackendState>,
+    child: Mutex<Option<tokio::process::Child>>,
+    monitor_task: Mutex<Option<tokio::task::JoinHandle<()>>>,
+}
+
+impl BackendSupervisor {
+    fn new() -> Self {
+        BackendSupervisor {
+            state: Mutex::new(BackendState::Stopped),
+            child: Mutex::new(None),
+            monitor_task: Mutex::new(None),
+        }
+    }
+}
+
+fn set_backend_state(app_handle: &tauri::AppHandle, supervisor: &BackendSupervisor, new_state: BackendState, detail: Option<String>) -> Result<(), String> {
+    *supervisor.state.lock().map_err(|_| "Backend supervisor lock poisoned".to_string())? = new_state;
+    let _ = app_handle.emit_all("backend_state", BackendStateEvent { state: new_state, detail });
+    Ok(())
+}
+
+async fn wait_for_health(client: &Client, health_url: &str, timeout: Duration) -> bool {
+    let deadline = tokio::time::Instant::now() + timeout;
+    while tokio::time::Instant::now() < deadline {
+        if client.get(health_url).send().await.map(|r| r.status().is_success()).unwrap_or(false) {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    false
+}
+
+/// Supervises the backend child process: waits for it to become healthy,
+/// then watches for an unexpected exit and restarts it with an exponential
+/// backoff (1s, 2s, 4s, ... capped at 32s), up to `max_restart_attempts`,
+/// emitting `backend_state` events
+/// throughout.
+async fn monitor_backend(app_handle: tauri::AppHandle, process_config: BackendProcessConfig, http_config: HttpConfig) {
+    let health_url = format!("{}{}", http_config.base_url, process_config.health_path);
+    let client = Client::new();
+    let supervisor = app_handle.state::<BackendSupervisor>();
+
+    if !wait_for_health(&client, &health_url, Duration::from_secs(process_config.startup_timeout_secs)).await {
+        let _ = set_backend_state(&app_handle, &supervisor, BackendState::Crashed, Some("Backend did not become healthy before the startup timeout".to_string()));
+        return;
+    }
+    let _ = set_backend_state(&app_handle, &supervisor, BackendState::Ready, None);
+
+    let mut restart_attempts: u32 = 0;
+    loop {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        let exited = match supervisor.child.lock() {
+            Ok(mut child_guard) => match child_guard.as_mut() {
+                Some(child) => child.try_wait().ok().flatten().is_some(),
+                None => true,
+            },
+            Err(_) => return,
+        };
+
+        if !exited {
+            continue;
+        }
+
+        if restart_attempts >= process_config.max_restart_attempts {
+            let _ = set_backend_state(&app_handle, &supervisor, BackendState::Crashed, Some("Backend crashed repeatedly; giving up".to_string()));
+            return;
+        }
+
+        let _ = set_backend_state(&app_handle, &supervisor, BackendState::Crashed, Some("Backend process exited unexpectedly; restarting".to_string()));
+        tokio::time::sleep(Duration::from_secs(2u64.saturating_pow(restart_attempts.min(5)))).await;
+        restart_attempts += 1;
+        let _ = set_backend_state(&app_handle, &supervisor, BackendState::Starting, None);
+
+        let spawned = tokio::process::Command::new(&process_config.command)
+            .args(&process_config.args)
+            .kill_on_drop(true)
+            .spawn();
+
+        match spawned {
+            Ok(new_child) => {
+                if let Ok(mut child_guard) = supervisor.child.lock() {
+                    *child_guard = Some(new_child);
+                }
+                if wait_for_health(&client, &health_url, Duration::from_secs(process_config.startup_timeout_secs)).await {
+                    let _ = set_backend_state(&app_handle, &supervisor, BackendState::Ready, None);
+                } else {
+                    let _ = set_backend_state(&app_handle, &supervisor, BackendState::Crashed, Some("Restarted backend did not become healthy".to_string()));
+                    return;
+                }
+            }
+            Err(e) => {
+                let _ = set_backend_state(&app_handle, &supervisor, BackendState::Crashed, Some(format!("Failed to restart backend: {}", e)));
+                return;
+            }
+        }
+    }
+}
+
+/// Tracks in-flight streaming generation jobs so `cancel_generation` can
+/// abort the backing Tokio task by job id.
+struct GenerationJobs {
+    tasks: Mutex<std::collections::HashMap<String, tokio::task::JoinHandle<()>>>,
+}
+
+impl GenerationJobs {
+    fn new() -> Self {
+        GenerationJobs { tasks: Mutex::new(std::collections::HashMap::new()) }
+    }
+}
+
+/// A generated artifact (source tree export, diagram, zip, ...) cached so
+/// the `deepcode://` protocol handler can serve it to the webview without
+/// round-tripping the bytes through IPC as base64.
+struct CachedArtifact {
+    mime: String,
+    bytes: Vec<u8>,
+}
+
+/// Artifacts keyed by `<job_id>/<path>`, populated as generation results
+/// come in and served by the `deepcode://artifact/<job_id>/<path>` scheme.
+struct ArtifactCache {
+    artifacts: Mutex<std::collections::HashMap<String, CachedArtifact>>,
+}
+
+impl ArtifactCache {
+    fn new() -> Self {
+        ArtifactCache { artifacts: Mutex::new(std::collections::HashMap::new()) }
+    }
+
+    fn insert(&self, job_id: &str, path: &str, mime: String, bytes: Vec<u8>) {
+        let key = format!("{}/{}", job_id, path);
+        if let Ok(mut artifacts) = self.artifacts.lock() {
+            artifacts.insert(key, CachedArtifact { mime, bytes });
+        }
+    }
+
+    /// Removes and returns the artifact for a one-shot preview; callers
+    /// that need it again must re-fetch/re-cache it.
+    fn take(&self, job_id: &str, path: &str) -> Option<CachedArtifact> {
+        let key = format!("{}/{}", job_id, path);
+        self.artifacts.lock().ok()?.remove(&key)
+    }
+}
+
+fn guess_artifact_mime(path: &str) -> &'static str {
+    let lower = path.to_ascii_lowercase();
+    if lower.ends_with(".zip") {
+        "application/zip"
+    } else if lower.ends_with(".png") {
+        "image/png"
+    } else if lower.ends_with(".jpg") || lower.ends_with(".jpeg") {
+        "image/jpeg"
+    } else if lower.ends_with(".svg") {
+        "image/svg+xml"
+    } else if lower.ends_with(".json") {
+        "application/json"
+    } else if lower.ends_with(".html") {
+        "text/html"
+    } else {
+        "application/octet-stream"
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct ProcessingHistoryEntry {
     id: String,
@@ -36,111 +504,673 @@ struct SystemDiagnostics {
 
 // --- Helper Functions ---
 
-async fn make_post_request<T: Serialize, R: for<'de> Deserialize<'de>>(endpoint: &str, body: &T) -> Result<R, String> {
-    let client = Client::new();
-    let url = format!("http://localhost:8000{}", endpoint);
+/// Blocks up to a few seconds for the backend to be usable, returning a
+/// clear error instead of letting the caller hang on a connection that
+/// will never come up. If the supervisor hasn't been told to spawn
+/// anything (`Stopped`/`Crashed`), this also probes `/health` directly —
+/// covering the previously-supported case of a backend already running
+/// externally on the configured `base_url` that `start_backend` was never
+/// asked to launch.
+async fn ensure_backend_ready(app_handle: &tauri::AppHandle, http_state: &HttpState, backend: &BackendSupervisor) -> Result<(), String> {
+    let current = *backend.state.lock().map_err(|_| "Backend supervisor lock poisoned".to_string())?;
+    if current == BackendState::Ready {
+        return Ok(());
+    }
 
-    let response = client.post(&url)
-        .json(body)
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
+    if current == BackendState::Stopped || current == BackendState::Crashed {
+        let config = http_state.config.lock().map_err(|_| "HTTP config lock poisoned".to_string())?.clone();
+        let probe_client = Client::builder()
+            .connect_timeout(Duration::from_secs(config.connect_timeout_secs))
+            .build()
+            .unwrap_or_else(|_| Client::new());
+        let health_url = format!("{}/health", config.base_url);
 
-    if !response.status().is_success() {
-        return Err(format!("API Error: {}", response.status()));
+        if wait_for_health(&probe_client, &health_url, Duration::from_secs(2)).await {
+            return set_backend_state(app_handle, backend, BackendState::Ready, Some("Detected an already-running backend".to_string()));
+        }
     }
 
-    response.json::<R>()
-        .await
-        .map_err(|e| format!("Failed to parse response: {}", e))
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+    loop {
+        let state = *backend.state.lock().map_err(|_| "Backend supervisor lock poisoned".to_string())?;
+        if state == BackendState::Ready {
+            return Ok(());
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Err(format!("Backend not ready (state: {:?}); start the backend and try again", state));
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
 }
 
-async fn make_get_request<R: for<'de> Deserialize<'de>>(endpoint: &str) -> Result<R, String> {
-    let client = Client::new();
-    let url = format!("http://localhost:8000{}", endpoint);
+/// Runs a single HTTP call described by `options` against `base_url + endpoint`,
+/// retrying on transport-level failures up to `retry_count` times with a short
+/// linear backoff. A per-call timeout/redirect override triggers a one-off
+/// client build; otherwise the shared managed client is reused.
+async fn execute_request<T: Serialize>(
+    app_handle: &tauri::AppHandle,
+    state: &HttpState,
+    backend: &BackendSupervisor,
+    endpoint: &str,
+    options: HttpOptions,
+    body: Option<&T>,
+) -> Result<HttpResponseBody, String> {
+    ensure_backend_ready(app_handle, state, backend).await?;
 
-    let response = client.get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
+    let config = state.config.lock().map_err(|_| "HTTP config lock poisoned".to_string())?.clone();
+    let url = format!("{}{}", config.base_url, endpoint);
 
-    if !response.status().is_success() {
-        return Err(format!("API Error: {}", response.status()));
+    let needs_custom_client = options.connect_timeout.is_some()
+        || options.read_timeout.is_some()
+        || options.timeout.is_some()
+        || !options.follow_redirects
+        || options.max_redirections.is_some()
+        || options.allow_compression.is_some();
+
+    let client = if needs_custom_client {
+        let mut builder = Client::builder()
+            .connect_timeout(options.connect_timeout.unwrap_or(Duration::from_secs(config.connect_timeout_secs)))
+            .gzip(options.allow_compression.unwrap_or(config.allow_compression));
+
+        builder = if !options.follow_redirects {
+            builder.redirect(reqwest::redirect::Policy::none())
+        } else {
+            builder.redirect(reqwest::redirect::Policy::limited(
+                options.max_redirections.unwrap_or(config.max_redirects),
+            ))
+        };
+
+        if let Some(timeout) = options.timeout.or(options.read_timeout) {
+            builder = builder.timeout(timeout);
+        } else {
+            builder = builder.timeout(Duration::from_secs(config.read_timeout_secs));
+        }
+
+        builder.build().map_err(|e| format!("Failed to build HTTP client: {}", e))?
+    } else {
+        state.client.lock().map_err(|_| "HTTP client lock poisoned".to_string())?.clone()
+    };
+
+    let mut attempts_left = config.retry_count + 1;
+    let mut attempt = 0u32;
+    loop {
+        attempts_left -= 1;
+        attempt += 1;
+
+        let mut request = client.request(options.method.clone(), &url);
+        for (name, value) in &options.headers {
+            request = request.header(name, value);
+        }
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+
+        let result = request.send().await;
+
+        let response = match result {
+            Ok(response) => response,
+            Err(e) => {
+                if attempts_left == 0 {
+                    return Err(format!("Request failed: {}", e));
+                }
+                tokio::time::sleep(Duration::from_millis(250 * attempt as u64)).await;
+                continue;
+            }
+        };
+
+        if !response.status().is_success() {
+            return Err(format!("API Error: {}", response.status()));
+        }
+
+        return match options.response_type {
+            ResponseType::Json => {
+                let value = response.json::<serde_json::Value>()
+                    .await
+                    .map_err(|e| format!("Failed to parse response: {}", e))?;
+                Ok(HttpResponseBody::Json(value))
+            }
+            ResponseType::Text => {
+                let text = response.text()
+                    .await
+                    .map_err(|e| format!("Failed to read response: {}", e))?;
+                Ok(HttpResponseBody::Text(text))
+            }
+            ResponseType::Binary => {
+                let bytes = response.bytes()
+                    .await
+                    .map_err(|e| format!("Failed to read response: {}", e))?;
+                Ok(HttpResponseBody::Binary(bytes.to_vec()))
+            }
+        };
+    }
+}
+
+async fn make_post_request<T: Serialize, R: for<'de> Deserialize<'de>>(app_handle: &tauri::AppHandle, state: &HttpState, backend: &BackendSupervisor, endpoint: &str, body: &T) -> Result<R, String> {
+    let options = HttpOptions { method: reqwest::Method::POST, ..Default::default() };
+    match execute_request(app_handle, state, backend, endpoint, options, Some(body)).await? {
+        HttpResponseBody::Json(value) => serde_json::from_value(value).map_err(|e| format!("Failed to parse response: {}", e)),
+        _ => Err("Expected a JSON response".to_string()),
     }
+}
 
-    response.json::<R>()
-        .await
-        .map_err(|e| format!("Failed to parse response: {}", e))
+async fn make_get_request<R: for<'de> Deserialize<'de>>(app_handle: &tauri::AppHandle, state: &HttpState, backend: &BackendSupervisor, endpoint: &str) -> Result<R, String> {
+    let options = HttpOptions { method: reqwest::Method::GET, ..Default::default() };
+    match execute_request::<()>(app_handle, state, backend, endpoint, options, None).await? {
+        HttpResponseBody::Json(value) => serde_json::from_value(value).map_err(|e| format!("Failed to parse response: {}", e)),
+        _ => Err("Expected a JSON response".to_string()),
+    }
 }
 
 // --- Tauri Commands ---
 
 #[tauri::command]
-async fn generate_questions(initial_requirement: String) -> Result<Vec<Question>, String> {
+async fn generate_questions(initial_requirement: String, app_handle: tauri::AppHandle, state: tauri::State<'_, HttpState>, backend: tauri::State<'_, BackendSupervisor>) -> Result<Vec<Question>, String> {
     #[derive(Serialize)]
     struct Request { initial_requirement: String }
 
-    make_post_request("/generate_questions", &Request { initial_requirement }).await
+    make_post_request(&app_handle, &state, &backend, "/generate_questions", &Request { initial_requirement }).await
 }
 
 #[tauri::command]
-async fn generate_detailed_requirements(initial_requirement: String, answers: std::collections::HashMap<String, String>) -> Result<String, String> {
+async fn generate_detailed_requirements(initial_requirement: String, answers: std::collections::HashMap<String, String>, app_handle: tauri::AppHandle, state: tauri::State<'_, HttpState>, backend: tauri::State<'_, BackendSupervisor>) -> Result<String, String> {
     #[derive(Serialize)]
     struct Request { initial_requirement: String, answers: std::collections::HashMap<String, String> }
 
-    make_post_request("/generate_requirements", &Request { initial_requirement, answers }).await
+    make_post_request(&app_handle, &state, &backend, "/generate_requirements", &Request { initial_requirement, answers }).await
+}
+
+/// Streaming variant of `generate_detailed_requirements`: returns a job id
+/// immediately and streams `generation_progress` / `generation_done` /
+/// `generation_error` events to the frontend as the backend produces
+/// newline-delimited JSON frames over `/generate_requirements/stream`.
+#[tauri::command]
+async fn generate_detailed_requirements_stream(
+    initial_requirement: String,
+    answers: std::collections::HashMap<String, String>,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, HttpState>,
+    backend: tauri::State<'_, BackendSupervisor>,
+    jobs: tauri::State<'_, GenerationJobs>,
+) -> Result<String, String> {
+    #[derive(Serialize)]
+    struct Request { initial_requirement: String, answers: std::collections::HashMap<String, String> }
+
+    ensure_backend_ready(&app_handle, &state, &backend).await?;
+
+    let job_id = Uuid::new_v4().to_string();
+    let config = state.config.lock().map_err(|_| "HTTP config lock poisoned".to_string())?.clone();
+    // The shared client carries an overall request `.timeout()`, which reqwest
+    // applies to the whole response body read — exactly wrong for a stream
+    // that can legitimately run for minutes. Build a client with only a
+    // connect timeout so the stream is never force-aborted mid-generation.
+    let client = Client::builder()
+        .connect_timeout(Duration::from_secs(config.connect_timeout_secs))
+        .gzip(config.allow_compression)
+        .build()
+        .map_err(|e| format!("Failed to build streaming HTTP client: {}", e))?;
+    let url = format!("{}/generate_requirements/stream", config.base_url);
+    let body = Request { initial_requirement, answers };
+    let body_json = serde_json::to_value(&body).map_err(|e| format!("Failed to encode request: {}", e))?;
+
+    let task_job_id = job_id.clone();
+    let handle = tokio::spawn(async move {
+        run_generation_stream(app_handle, client, url, body_json, task_job_id).await;
+    });
+
+    jobs.tasks.lock().map_err(|_| "Generation job lock poisoned".to_string())?.insert(job_id.clone(), handle);
+
+    Ok(job_id)
+}
+
+/// Decodes and stores any artifacts attached to a stream frame in the
+/// managed `ArtifactCache`, keyed by this job's id, so the webview can load
+/// them via `deepcode://artifact/<job_id>/<path>` without a backend round
+/// trip. Malformed base64 is skipped rather than failing the whole job.
+/// Returns the artifacts actually cached, so the caller can surface them to
+/// the frontend in the `generation_progress`/`generation_done` payloads.
+fn cache_stream_artifacts(app_handle: &tauri::AppHandle, job_id: &str, artifacts: &[GeneratedArtifactFrame]) -> Vec<GenerationArtifactInfo> {
+    if artifacts.is_empty() {
+        return Vec::new();
+    }
+
+    let cache = app_handle.state::<ArtifactCache>();
+    let mut cached = Vec::new();
+    for artifact in artifacts {
+        match base64::engine::general_purpose::STANDARD.decode(&artifact.content_base64) {
+            Ok(bytes) => {
+                let mime = artifact.mime.clone().unwrap_or_else(|| guess_artifact_mime(&artifact.path).to_string());
+                cache.insert(job_id, &artifact.path, mime.clone(), bytes);
+                cached.push(GenerationArtifactInfo { path: artifact.path.clone(), mime });
+            }
+            Err(_) => continue,
+        }
+    }
+    cached
+}
+
+/// Removes `job_id` from the managed `GenerationJobs` map. Called once a job
+/// reaches a terminal state so the map doesn't grow unbounded across runs
+/// with dead `JoinHandle`s that only `cancel_generation` would otherwise
+/// clean up.
+fn finish_generation_job(app_handle: &tauri::AppHandle, job_id: &str) {
+    if let Ok(mut tasks) = app_handle.state::<GenerationJobs>().tasks.lock() {
+        tasks.remove(job_id);
+    }
+}
+
+async fn run_generation_stream(app_handle: tauri::AppHandle, client: Client, url: String, body: serde_json::Value, job_id: String) {
+    let response = match client.post(&url).json(&body).send().await {
+        Ok(response) => response,
+        Err(e) => {
+            finish_generation_job(&app_handle, &job_id);
+            let _ = app_handle.emit_all("generation_error", GenerationErrorPayload { job_id, error: format!("Request failed: {}", e) });
+            return;
+        }
+    };
+
+    if !response.status().is_success() {
+        finish_generation_job(&app_handle, &job_id);
+        let _ = app_handle.emit_all("generation_error", GenerationErrorPayload { job_id, error: format!("API Error: {}", response.status()) });
+        return;
+    }
+
+    let mut stream = response.bytes_stream();
+    let mut line_buffer = String::new();
+    let mut accumulated_text = String::new();
+    let mut tokens_generated: Option<u64> = None;
+    let mut artifacts: Vec<GenerationArtifactInfo> = Vec::new();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                finish_generation_job(&app_handle, &job_id);
+                let _ = app_handle.emit_all("generation_error", GenerationErrorPayload { job_id, error: format!("Stream error: {}", e) });
+                return;
+            }
+        };
+
+        line_buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+        while let Some(newline_pos) = line_buffer.find('\n') {
+            let line = line_buffer[..newline_pos].trim().to_string();
+            line_buffer.drain(..=newline_pos);
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let frame: GenerationStreamFrame = match serde_json::from_str(&line) {
+                Ok(frame) => frame,
+                Err(e) => {
+                    finish_generation_job(&app_handle, &job_id);
+                    let _ = app_handle.emit_all("generation_error", GenerationErrorPayload { job_id, error: format!("Failed to parse stream frame: {}", e) });
+                    return;
+                }
+            };
+
+            accumulated_text.push_str(&frame.text);
+            if frame.tokens.is_some() {
+                tokens_generated = frame.tokens;
+            }
+
+            artifacts.extend(cache_stream_artifacts(&app_handle, &job_id, &frame.artifacts));
+
+            if frame.done {
+                finish_generation_job(&app_handle, &job_id);
+                let _ = app_handle.emit_all("generation_done", GenerationDone { job_id, result: accumulated_text, artifacts });
+                return;
+            }
+
+            let _ = app_handle.emit_all("generation_progress", GenerationProgress {
+                job_id: job_id.clone(),
+                phase: frame.phase,
+                partial_text: accumulated_text.clone(),
+                tokens_generated,
+                artifacts: artifacts.clone(),
+            });
+        }
+    }
+
+    finish_generation_job(&app_handle, &job_id);
+    let _ = app_handle.emit_all("generation_done", GenerationDone { job_id, result: accumulated_text, artifacts });
 }
 
+/// Aborts an in-flight streaming generation job started by
+/// `generate_detailed_requirements_stream`, emitting a terminal
+/// `generation_error` so the frontend's in-flight stream state doesn't hang
+/// forever waiting for a `generation_done`/`generation_error` that would
+/// otherwise never come. A no-op if the job already finished or never
+/// existed.
 #[tauri::command]
-async fn edit_requirements(current_requirements: String, feedback: String) -> Result<String, String> {
+fn cancel_generation(job_id: String, app_handle: tauri::AppHandle, jobs: tauri::State<'_, GenerationJobs>) -> Result<(), String> {
+    let mut tasks = jobs.tasks.lock().map_err(|_| "Generation job lock poisoned".to_string())?;
+    if let Some(handle) = tasks.remove(&job_id) {
+        handle.abort();
+        drop(tasks);
+        let _ = app_handle.emit_all("generation_error", GenerationErrorPayload { job_id, error: "Generation cancelled".to_string() });
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn edit_requirements(current_requirements: String, feedback: String, app_handle: tauri::AppHandle, state: tauri::State<'_, HttpState>, backend: tauri::State<'_, BackendSupervisor>) -> Result<String, String> {
     #[derive(Serialize)]
     struct Request { current_requirements: String, feedback: String }
 
-    make_post_request("/edit_requirements", &Request { current_requirements, feedback }).await
+    make_post_request(&app_handle, &state, &backend, "/edit_requirements", &Request { current_requirements, feedback }).await
 }
 
 #[tauri::command]
-async fn get_processing_history() -> Result<Vec<ProcessingHistoryEntry>, String> {
-    make_get_request("/processing_history").await
+async fn get_processing_history(app_handle: tauri::AppHandle, state: tauri::State<'_, HttpState>, backend: tauri::State<'_, BackendSupervisor>) -> Result<Vec<ProcessingHistoryEntry>, String> {
+    make_get_request(&app_handle, &state, &backend, "/processing_history").await
 }
 
 #[tauri::command]
-async fn clear_processing_history() -> Result<(), String> {
-    let client = Client::new();
-    let url = "http://localhost:8000/processing_history";
+async fn clear_processing_history(app_handle: tauri::AppHandle, state: tauri::State<'_, HttpState>, backend: tauri::State<'_, BackendSupervisor>) -> Result<(), String> {
+    // Text rather than Json: a DELETE that replies 204/200 with an empty body
+    // would otherwise fail `response.json()` parsing for a response we don't
+    // care about anyway.
+    let options = HttpOptions { method: reqwest::Method::DELETE, response_type: ResponseType::Text, ..Default::default() };
+    execute_request::<()>(&app_handle, &state, &backend, "/processing_history", options, None).await?;
+    Ok(())
+}
 
-    let response = client.delete(url)
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
+#[tauri::command]
+async fn get_system_diagnostics(app_handle: tauri::AppHandle, state: tauri::State<'_, HttpState>, backend: tauri::State<'_, BackendSupervisor>) -> Result<SystemDiagnostics, String> {
+    make_get_request(&app_handle, &state, &backend, "/system_diagnostics").await
+}
 
-    if !response.status().is_success() {
-        return Err(format!("API Error: {}", response.status()));
+#[tauri::command]
+async fn reset_application_state(app_handle: tauri::AppHandle, state: tauri::State<'_, HttpState>, backend: tauri::State<'_, BackendSupervisor>) -> Result<(), String> {
+    // Text rather than Json: a reset endpoint that replies 204/200 with an
+    // empty body would otherwise fail `response.json()` parsing for a
+    // response we don't care about anyway.
+    let options = HttpOptions { method: reqwest::Method::POST, response_type: ResponseType::Text, ..Default::default() };
+    execute_request::<()>(&app_handle, &state, &backend, "/reset_state", options, None).await?;
+    Ok(())
+}
+
+#[tauri::command]
+fn get_backend_url(state: tauri::State<'_, HttpState>) -> Result<String, String> {
+    Ok(state.config.lock().map_err(|_| "HTTP config lock poisoned".to_string())?.base_url.clone())
+}
+
+#[tauri::command]
+fn set_backend_url(state: tauri::State<'_, HttpState>, base_url: String) -> Result<(), String> {
+    let mut config = state.config.lock().map_err(|_| "HTTP config lock poisoned".to_string())?;
+    config.base_url = base_url;
+    let new_client = config.build_client();
+    *state.client.lock().map_err(|_| "HTTP client lock poisoned".to_string())? = new_client;
+    Ok(())
+}
+
+// --- Backend Lifecycle ---
+
+/// Launches the Python backend as a supervised child process and spawns a
+/// monitor task that waits for it to become healthy, then restarts it on
+/// an unexpected crash. A no-op if the backend is already starting or
+/// ready.
+#[tauri::command]
+async fn start_backend(
+    app_handle: tauri::AppHandle,
+    http_state: tauri::State<'_, HttpState>,
+    backend: tauri::State<'_, BackendSupervisor>,
+) -> Result<(), String> {
+    {
+        let current = *backend.state.lock().map_err(|_| "Backend supervisor lock poisoned".to_string())?;
+        if current == BackendState::Starting || current == BackendState::Ready {
+            return Ok(());
+        }
     }
 
+    let process_config = BackendProcessConfig::load_from_app_config(&app_handle);
+    let http_config = http_state.config.lock().map_err(|_| "HTTP config lock poisoned".to_string())?.clone();
+
+    set_backend_state(&app_handle, &backend, BackendState::Starting, None)?;
+
+    let child = tokio::process::Command::new(&process_config.command)
+        .args(&process_config.args)
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| {
+            let _ = set_backend_state(&app_handle, &backend, BackendState::Crashed, Some(format!("Failed to spawn backend process: {}", e)));
+            format!("Failed to spawn backend process: {}", e)
+        })?;
+
+    *backend.child.lock().map_err(|_| "Backend supervisor lock poisoned".to_string())? = Some(child);
+
+    let monitor_app_handle = app_handle.clone();
+    let monitor_handle = tokio::spawn(async move {
+        monitor_backend(monitor_app_handle, process_config, http_config).await;
+    });
+    *backend.monitor_task.lock().map_err(|_| "Backend supervisor lock poisoned".to_string())? = Some(monitor_handle);
+
     Ok(())
 }
 
+/// Aborts the monitor task and kills the supervised backend process, if
+/// any. A no-op if the backend is already stopped.
 #[tauri::command]
-async fn get_system_diagnostics() -> Result<SystemDiagnostics, String> {
-    make_get_request("/system_diagnostics").await
+async fn stop_backend(app_handle: tauri::AppHandle, backend: tauri::State<'_, BackendSupervisor>) -> Result<(), String> {
+    if let Some(task) = backend.monitor_task.lock().map_err(|_| "Backend supervisor lock poisoned".to_string())?.take() {
+        task.abort();
+    }
+
+    let mut child_guard = backend.child.lock().map_err(|_| "Backend supervisor lock poisoned".to_string())?;
+    if let Some(mut child) = child_guard.take() {
+        let _ = child.start_kill();
+    }
+    drop(child_guard);
+
+    set_backend_state(&app_handle, &backend, BackendState::Stopped, None)
 }
 
 #[tauri::command]
-async fn reset_application_state() -> Result<(), String> {
-    let client = Client::new();
-    let url = "http://localhost:8000/reset_state";
+fn backend_status(backend: tauri::State<'_, BackendSupervisor>) -> Result<BackendState, String> {
+    Ok(*backend.state.lock().map_err(|_| "Backend supervisor lock poisoned".to_string())?)
+}
 
-    let response = client.post(url)
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
+// --- Diagnostics ---
 
-    if !response.status().is_success() {
-        return Err(format!("API Error: {}", response.status()));
+/// Resolves a backend-configured log path against the project directory,
+/// mirroring how `read_config`/`read_secrets` locate `mcp_agent.config.yaml`.
+fn resolve_log_path(app_handle: &tauri::AppHandle, log_path: &str) -> Option<std::path::PathBuf> {
+    let configured = std::path::PathBuf::from(log_path);
+    if configured.is_absolute() {
+        return Some(configured);
     }
 
-    Ok(())
+    app_handle.path_resolver()
+        .resolve_resource(format!("../../{}", log_path))
+        .or_else(|| {
+            let mut path = std::env::current_dir().unwrap_or_default();
+            if path.ends_with("src-tauri") {
+                path.pop();
+                path.pop();
+            } else if path.ends_with("desktop-ui") {
+                path.pop();
+            }
+            path.push(log_path);
+            Some(path)
+        })
+}
+
+fn tail_local_log(path: &std::path::Path, line_count: usize) -> Result<String, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Could not read local log file {}: {}", path.display(), e))?;
+    let lines: Vec<&str> = content.lines().collect();
+    let start = lines.len().saturating_sub(line_count);
+    Ok(lines[start..].join("\n"))
+}
+
+/// Fetches the most recent backend log lines so failures during
+/// generation can be inspected without shelling into the server. This is
+/// deliberately NOT gated on backend readiness: logs are most needed right
+/// after the backend has crashed, so a failed HTTP fetch falls back to
+/// tailing the backend's known local log file instead of erroring out.
+#[tauri::command]
+async fn get_backend_logs(lines: Option<usize>, app_handle: tauri::AppHandle, state: tauri::State<'_, HttpState>) -> Result<String, String> {
+    let line_count = lines.unwrap_or(200);
+    let config = state.config.lock().map_err(|_| "HTTP config lock poisoned".to_string())?.clone();
+
+    let client = Client::builder()
+        .connect_timeout(Duration::from_secs(config.connect_timeout_secs))
+        .timeout(Duration::from_secs(5))
+        .build()
+        .unwrap_or_else(|_| Client::new());
+    let url = format!("{}/backend_logs?lines={}", config.base_url, line_count);
+
+    if let Ok(response) = client.get(&url).send().await {
+        if response.status().is_success() {
+            if let Ok(text) = response.text().await {
+                return Ok(text);
+            }
+        }
+    }
+
+    let process_config = BackendProcessConfig::load_from_app_config(&app_handle);
+    match resolve_log_path(&app_handle, &process_config.log_path) {
+        Some(path) => tail_local_log(&path, line_count),
+        None => Err("Backend logs unavailable: could not reach the backend and no local log file was found".to_string()),
+    }
+}
+
+/// Walks a YAML value and blanks out any string leaf whose key looks like
+/// it holds a credential, so config can be safely attached to bug reports.
+fn redact_secrets(value: &mut serde_yaml::Value) {
+    const SENSITIVE_MARKERS: [&str; 6] = ["key", "token", "secret", "password", "api_key", "credential"];
+
+    match value {
+        serde_yaml::Value::Mapping(mapping) => {
+            for (key, entry) in mapping.iter_mut() {
+                let key_name = key.as_str().unwrap_or("").to_ascii_lowercase();
+                if SENSITIVE_MARKERS.iter().any(|marker| key_name.contains(marker)) && entry.is_string() {
+                    *entry = serde_yaml::Value::String("***REDACTED***".to_string());
+                } else {
+                    redact_secrets(entry);
+                }
+            }
+        }
+        serde_yaml::Value::Sequence(sequence) => {
+            for entry in sequence.iter_mut() {
+                redact_secrets(entry);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Bundles the latest backend log, the redacted `mcp_agent.config.yaml`,
+/// `SystemDiagnostics`, and processing history into a single zip so a
+/// user can attach one file to a bug report.
+#[tauri::command]
+async fn create_diagnostic_bundle(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, HttpState>,
+    backend: tauri::State<'_, BackendSupervisor>,
+) -> Result<String, String> {
+    let backend_logs = get_backend_logs(None, app_handle.clone(), state.clone()).await.unwrap_or_else(|e| format!("Could not retrieve backend logs: {}", e));
+
+    let redacted_config = match read_config(app_handle.clone()) {
+        Ok(content) => match serde_yaml::from_str::<serde_yaml::Value>(&content) {
+            Ok(mut value) => {
+                redact_secrets(&mut value);
+                serde_yaml::to_string(&value).unwrap_or(content)
+            }
+            Err(_) => content,
+        },
+        Err(e) => format!("Could not read mcp_agent.config.yaml: {}", e),
+    };
+
+    let diagnostics = get_system_diagnostics(app_handle.clone(), state.clone(), backend.clone()).await.ok();
+    let diagnostics_json = diagnostics
+        .map(|d| serde_json::to_string_pretty(&d).unwrap_or_default())
+        .unwrap_or_else(|| "Could not retrieve system diagnostics".to_string());
+
+    let history = get_processing_history(app_handle.clone(), state, backend).await.unwrap_or_default();
+    let history_json = serde_json::to_string_pretty(&history).unwrap_or_default();
+
+    let mut bundle_path = std::env::temp_dir();
+    bundle_path.push(format!("deepcode-diagnostics-{}.zip", Uuid::new_v4()));
+
+    let file = fs::File::create(&bundle_path).map_err(|e| format!("Failed to create bundle file: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file("backend.log", options).map_err(|e| e.to_string())?;
+    zip.write_all(backend_logs.as_bytes()).map_err(|e| e.to_string())?;
+
+    zip.start_file("mcp_agent.config.yaml", options).map_err(|e| e.to_string())?;
+    zip.write_all(redacted_config.as_bytes()).map_err(|e| e.to_string())?;
+
+    zip.start_file("system_diagnostics.json", options).map_err(|e| e.to_string())?;
+    zip.write_all(diagnostics_json.as_bytes()).map_err(|e| e.to_string())?;
+
+    zip.start_file("processing_history.json", options).map_err(|e| e.to_string())?;
+    zip.write_all(history_json.as_bytes()).map_err(|e| e.to_string())?;
+
+    zip.finish().map_err(|e| format!("Failed to finalize bundle: {}", e))?;
+
+    Ok(bundle_path.to_string_lossy().to_string())
+}
+
+// --- Artifact Protocol ---
+
+/// Handles `deepcode://artifact/<job_id>/<path>` requests from the
+/// webview: serves from `ArtifactCache` when present (removing the entry,
+/// since previews are one-shot), otherwise falls back to fetching
+/// `<base_url>/artifacts/<job_id>/<path>` from the backend.
+fn handle_artifact_request(
+    app: &tauri::AppHandle,
+    request: &tauri::http::Request,
+) -> Result<tauri::http::Response, Box<dyn std::error::Error>> {
+    let uri = request.uri();
+    let without_scheme = uri.trim_start_matches("deepcode://");
+    let mut segments = without_scheme.splitn(2, '/');
+    let host = segments.next().unwrap_or("");
+    let rest = segments.next().unwrap_or("").trim_start_matches('/');
+
+    if host != "artifact" || rest.is_empty() {
+        return tauri::http::ResponseBuilder::new().status(404).body(Vec::new());
+    }
+
+    let mut rest_parts = rest.splitn(2, '/');
+    let job_id = rest_parts.next().unwrap_or("").to_string();
+    let path = rest_parts.next().unwrap_or("").to_string();
+
+    if job_id.is_empty() || path.is_empty() {
+        return tauri::http::ResponseBuilder::new().status(404).body(Vec::new());
+    }
+
+    let cache = app.state::<ArtifactCache>();
+    if let Some(artifact) = cache.take(&job_id, &path) {
+        return tauri::http::ResponseBuilder::new()
+            .status(200)
+            .mimetype(&artifact.mime)
+            .body(artifact.bytes);
+    }
+
+    let http_state = app.state::<HttpState>();
+    let config = http_state.config.lock().map_err(|_| "HTTP config lock poisoned")?.clone();
+    let client = http_state.client.lock().map_err(|_| "HTTP client lock poisoned")?.clone();
+    let url = format!("{}/artifacts/{}/{}", config.base_url, job_id, path);
+
+    let fetch_result: Result<Vec<u8>, String> = tauri::async_runtime::block_on(async move {
+        let response = client.get(&url).send().await.map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("API Error: {}", response.status()));
+        }
+        let bytes = response.bytes().await.map_err(|e| e.to_string())?;
+        Ok(bytes.to_vec())
+    });
+
+    match fetch_result {
+        Ok(bytes) => {
+            let mime = guess_artifact_mime(&path);
+            tauri::http::ResponseBuilder::new().status(200).mimetype(mime).body(bytes)
+        }
+        Err(_) => tauri::http::ResponseBuilder::new().status(404).body(Vec::new()),
+    }
 }
 
 // --- Existing Config Commands ---
@@ -239,6 +1269,15 @@ fn write_secrets(app_handle: tauri::AppHandle, content: String) -> Result<(), St
 
 fn main() {
     tauri::Builder::default()
+        .setup(|app| {
+            let config = HttpConfig::load_from_app_config(&app.handle());
+            app.manage(HttpState::new(config));
+            app.manage(GenerationJobs::new());
+            app.manage(ArtifactCache::new());
+            app.manage(BackendSupervisor::new());
+            Ok(())
+        })
+        .register_uri_scheme_protocol("deepcode", handle_artifact_request)
         .invoke_handler(tauri::generate_handler![
             read_config,
             write_config,
@@ -246,11 +1285,20 @@ fn main() {
             write_secrets,
             generate_questions,
             generate_detailed_requirements,
+            generate_detailed_requirements_stream,
+            cancel_generation,
             edit_requirements,
             get_processing_history,
             clear_processing_history,
             get_system_diagnostics,
-            reset_application_state
+            reset_application_state,
+            get_backend_url,
+            set_backend_url,
+            get_backend_logs,
+            create_diagnostic_bundle,
+            start_backend,
+            stop_backend,
+            backend_status
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");